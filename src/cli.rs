@@ -0,0 +1,212 @@
+use crate::{FractalKind, ParseFractalKindError};
+use num::Complex;
+use std::str::FromStr;
+
+/// Splits `s` on the first occurrence of `separator` and parses each half as `T`.
+///
+/// # Parameters
+/// - `s`: input string, e.g. `"800x800"` or `"-0.4,0.0"`
+/// - `separator`: the character dividing the two fields, e.g. `'x'` or `','`
+///
+/// # Returns
+/// - `Some((first, second))`: when both halves parse as `T`
+/// - `None`: when `s` doesn't contain `separator` or either half fails to parse
+pub fn parse_pair<T: FromStr>(s: &str, separator: char) -> Option<(T, T)> {
+    let (first, second) = s.split_once(separator)?;
+    Some((first.trim().parse().ok()?, second.trim().parse().ok()?))
+}
+
+/// The image resolution and viewing rectangle on the complex plane, derived from a resolution, a center point, and
+/// a scale factor.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Viewport {
+    pub image_width: usize,
+    pub image_height: usize,
+    pub center: Complex<f32>,
+    pub dimensions: Complex<f32>,
+}
+impl Viewport {
+    /// # Parameters
+    /// - `image_width`, `image_height`: image resolution
+    /// - `center`: center complex point of the viewing rectangle
+    /// - `scale`: the viewing rectangle's diagonal length on the complex plane
+    pub fn new(image_width: usize, image_height: usize, center: Complex<f32>, scale: f32) -> Self {
+        let dimensions = Complex::new(image_width as f32, image_height as f32);
+        let dimensions = (dimensions / dimensions.norm()).scale(scale);
+
+        Self {
+            image_width,
+            image_height,
+            center,
+            dimensions,
+        }
+    }
+}
+
+/// Everything needed to drive a render headlessly, parsed from CLI-style arguments instead of interactive prompts.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RenderConfig {
+    pub viewport: Viewport,
+    pub iteration_max: usize,
+    pub fractal_kind: FractalKind,
+    pub output_path: String,
+}
+impl RenderConfig {
+    /// Builds a [`RenderConfig`] from an argument iterator (e.g. `std::env::args().skip(1)`), in the order
+    /// `WIDTHxHEIGHT re,im scale iteration_max fractal_kind output_path`.
+    ///
+    /// # Parameters
+    /// - `args`: the positional arguments, already stripped of the program name
+    ///
+    /// # Returns
+    /// - `Ok(render_config)`: when every field is present and parses
+    /// - `Err(parse_error)`: naming the first missing or invalid field
+    pub fn from_args(mut args: impl Iterator<Item = String>) -> Result<Self, ParseRenderConfigError> {
+        let resolution = args
+            .next()
+            .ok_or_else(|| ParseRenderConfigError("missing resolution (e.g. 800x800)".to_owned()))?;
+        let (image_width, image_height) = parse_pair::<usize>(&resolution, 'x').ok_or_else(|| {
+            ParseRenderConfigError(format!(
+                "invalid resolution '{resolution}' (expected WIDTHxHEIGHT)"
+            ))
+        })?;
+
+        let center_arg = args
+            .next()
+            .ok_or_else(|| ParseRenderConfigError("missing center (e.g. -0.4,0.0)".to_owned()))?;
+        let (center_re, center_im) = parse_pair::<f32>(&center_arg, ',').ok_or_else(|| {
+            ParseRenderConfigError(format!("invalid center '{center_arg}' (expected re,im)"))
+        })?;
+        let center = Complex::new(center_re, center_im);
+
+        let scale_arg = args
+            .next()
+            .ok_or_else(|| ParseRenderConfigError("missing scale".to_owned()))?;
+        let scale: f32 = scale_arg
+            .parse()
+            .map_err(|_| ParseRenderConfigError(format!("invalid scale '{scale_arg}'")))?;
+
+        let iteration_max_arg = args
+            .next()
+            .ok_or_else(|| ParseRenderConfigError("missing iteration_max".to_owned()))?;
+        let iteration_max: usize = iteration_max_arg.parse().map_err(|_| {
+            ParseRenderConfigError(format!("invalid iteration_max '{iteration_max_arg}'"))
+        })?;
+
+        let fractal_kind_arg = args
+            .next()
+            .ok_or_else(|| ParseRenderConfigError("missing fractal kind".to_owned()))?;
+        let fractal_kind: FractalKind = fractal_kind_arg
+            .parse()
+            .map_err(|err: ParseFractalKindError| ParseRenderConfigError(err.to_string()))?;
+
+        let output_path = args
+            .next()
+            .ok_or_else(|| ParseRenderConfigError("missing output path".to_owned()))?;
+
+        Ok(RenderConfig {
+            viewport: Viewport::new(image_width, image_height, center, scale),
+            iteration_max,
+            fractal_kind,
+            output_path,
+        })
+    }
+}
+
+/// Returned by [`RenderConfig::from_args`] when an argument is missing or doesn't parse.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseRenderConfigError(String);
+impl std::fmt::Display for ParseRenderConfigError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+impl std::error::Error for ParseRenderConfigError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_pair_splits_and_parses_both_halves() {
+        assert_eq!(parse_pair::<usize>("800x600", 'x'), Some((800, 600)));
+        assert_eq!(parse_pair::<f32>("-0.4,0.0", ','), Some((-0.4, 0.0)));
+    }
+
+    #[test]
+    fn parse_pair_rejects_missing_separator_or_bad_halves() {
+        assert_eq!(parse_pair::<usize>("800", 'x'), None);
+        assert_eq!(parse_pair::<usize>("800xnope", 'x'), None);
+    }
+
+    fn valid_args() -> Vec<String> {
+        [
+            "800x800",
+            "-0.4,0.0",
+            "4.0",
+            "500",
+            "mandelbrot",
+            "out.png",
+        ]
+        .into_iter()
+        .map(String::from)
+        .collect()
+    }
+
+    #[test]
+    fn from_args_parses_a_full_valid_argument_list() {
+        let config = RenderConfig::from_args(valid_args().into_iter()).unwrap();
+
+        assert_eq!(config.viewport.image_width, 800);
+        assert_eq!(config.viewport.image_height, 800);
+        assert_eq!(config.viewport.center, Complex::new(-0.4, 0.0));
+        assert_eq!(config.iteration_max, 500);
+        assert_eq!(config.fractal_kind, FractalKind::Mandelbrot);
+        assert_eq!(config.output_path, "out.png");
+    }
+
+    #[test]
+    fn from_args_reports_a_missing_field() {
+        let args = valid_args().into_iter().take(3).collect::<Vec<_>>();
+
+        let error = RenderConfig::from_args(args.into_iter()).unwrap_err();
+
+        assert_eq!(error.to_string(), "missing iteration_max");
+    }
+
+    #[test]
+    fn from_args_rejects_an_invalid_resolution() {
+        let mut args = valid_args();
+        args[0] = "800-800".to_owned();
+
+        let error = RenderConfig::from_args(args.into_iter()).unwrap_err();
+
+        assert_eq!(
+            error.to_string(),
+            "invalid resolution '800-800' (expected WIDTHxHEIGHT)"
+        );
+    }
+
+    #[test]
+    fn from_args_rejects_an_invalid_center() {
+        let mut args = valid_args();
+        args[1] = "-0.4;0.0".to_owned();
+
+        let error = RenderConfig::from_args(args.into_iter()).unwrap_err();
+
+        assert_eq!(error.to_string(), "invalid center '-0.4;0.0' (expected re,im)");
+    }
+
+    #[test]
+    fn from_args_rejects_an_invalid_fractal_kind() {
+        let mut args = valid_args();
+        args[4] = "not_a_fractal".to_owned();
+
+        let error = RenderConfig::from_args(args.into_iter()).unwrap_err();
+
+        assert_eq!(
+            error.to_string(),
+            "'not_a_fractal' is not a valid fractal kind (expected mandelbrot, mandelbrot_cubic, or burning_ship)"
+        );
+    }
+}
@@ -1,5 +1,8 @@
 use num::Complex;
 use rayon::iter::{IntoParallelIterator, ParallelIterator};
+use std::str::FromStr;
+
+pub mod cli;
 
 #[cfg(feature = "epaint")]
 pub mod epaint_adapter;
@@ -7,9 +10,85 @@ pub mod epaint_adapter;
 #[cfg(feature = "image")]
 pub mod image_adapter;
 
+pub mod pnm_adapter;
+
 #[cfg(test)]
 pub mod test;
 
+/// The numeric operations the render pipeline needs from a floating point type: everything in [`num::Float`], plus
+/// `Send`/`Sync` so it can cross the `rayon` thread pool. `f32` ([`DefaultFloat`]) is the crate's original, convenient
+/// default; `f64` trades speed for the extra mantissa bits deep zooms need.
+pub trait Float: num::Float + Send + Sync {}
+impl<T: num::Float + Send + Sync> Float for T {}
+
+/// The crate's default precision.
+pub type DefaultFloat = f32;
+
+/// Which iteration formula to use when computing escape time.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum FractalKind {
+    /// `z^2 + c`, the classic Mandelbrot set
+    #[default]
+    Mandelbrot,
+    /// `z^3 + c`
+    MandelbrotCubic,
+    /// `z^2 + c`, after folding `z` into the first quadrant (`z.re.abs()`, `z.im.abs()`) each iteration
+    BurningShip,
+}
+impl FractalKind {
+    /// Returns the per-pixel iteration closure for this fractal, with `c` baked in.
+    pub fn iteration_fn<T: Float>(&self, c: Complex<T>) -> impl FnMut(Complex<T>) -> Complex<T> {
+        let kind = *self;
+        move |z: Complex<T>| match kind {
+            FractalKind::Mandelbrot => z * z + c,
+            FractalKind::MandelbrotCubic => z * z * z + c,
+            FractalKind::BurningShip => {
+                let z = Complex::new(z.re.abs(), z.im.abs());
+                z * z + c
+            }
+        }
+    }
+
+    /// The squared escape radius to use as `escape_time`'s `bound` argument for this fractal at a given `c`.
+    /// `z^2 + c` (and the burning ship variant, which only folds `z` before squaring) diverges once `|z| > 2`, but
+    /// `z^3 + c` needs the larger `|z| > max(|c|, 2)` to guarantee divergence, since a large `|c|` can keep pushing
+    /// `z` back down below radius 2 for another iteration.
+    pub fn escape_radius_sqr<T: Float>(&self, c: Complex<T>) -> T {
+        let two = T::one() + T::one();
+        let radius = match self {
+            FractalKind::Mandelbrot | FractalKind::BurningShip => two,
+            FractalKind::MandelbrotCubic => c.norm().max(two),
+        };
+        radius * radius
+    }
+}
+impl FromStr for FractalKind {
+    type Err = ParseFractalKindError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "mandelbrot" => Ok(FractalKind::Mandelbrot),
+            "mandelbrotcubic" | "mandelbrot_cubic" | "cubic" => Ok(FractalKind::MandelbrotCubic),
+            "burningship" | "burning_ship" | "burning ship" => Ok(FractalKind::BurningShip),
+            _ => Err(ParseFractalKindError(s.to_owned())),
+        }
+    }
+}
+
+/// Returned by [`FractalKind::from_str`] when the input doesn't match any known fractal.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseFractalKindError(String);
+impl std::fmt::Display for ParseFractalKindError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "'{}' is not a valid fractal kind (expected mandelbrot, mandelbrot_cubic, or burning_ship)",
+            self.0
+        )
+    }
+}
+impl std::error::Error for ParseFractalKindError {}
+
 /// 8 bit r, g, b
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct Color([u8; 4]);
@@ -61,40 +140,41 @@ pub fn escape_time_to_grayscale(escape_time: Option<usize>) -> Color {
 ///
 /// # Parameters
 /// - `pixel_x`, `pixel_y`: pixel position. (top left pixel is `(0, 0)`)
-/// - `image_width`, `image_height`: image resolution  
+/// - `image_width`, `image_height`: image resolution
 /// - `center`: center complex point of a rectangle
 /// - `bottom_right`: bottom right complex point of a rectangle
 ///
 /// # Returns
 /// - [Complex]: A unique complex number calculated from params
-pub fn pixel_to_complex(
+pub fn pixel_to_complex<T: Float>(
     pixel_x: usize,
     pixel_y: usize,
     image_width: usize,
     image_height: usize,
-    center: Complex<f32>,
-    dimensions: Complex<f32>,
-) -> Complex<f32> {
+    center: Complex<T>,
+    dimensions: Complex<T>,
+) -> Complex<T> {
     let complex_plane_width = dimensions.re;
     let complex_plane_height = dimensions.im;
 
-    let horizontal_ratio = pixel_x as f32 / image_width as f32;
-    let vertical_ratio = pixel_y as f32 / image_height as f32;
+    let horizontal_ratio = T::from(pixel_x).unwrap() / T::from(image_width).unwrap();
+    let vertical_ratio = T::from(pixel_y).unwrap() / T::from(image_height).unwrap();
 
     let offset = Complex::new(
         complex_plane_width * horizontal_ratio,
         complex_plane_height * vertical_ratio,
     );
 
-    let top_left = center - dimensions / 2.0;
+    let two = T::one() + T::one();
+    let top_left = center - dimensions / two;
 
     top_left + offset
 }
 
-pub fn escape_time(
-    z0: Complex<f32>,
-    mut f: impl FnMut(Complex<f32>) -> Complex<f32>,
-    bound: f32,
+pub fn escape_time<T: Float>(
+    z0: Complex<T>,
+    mut f: impl FnMut(Complex<T>) -> Complex<T>,
+    bound: T,
     iteration_max: usize,
 ) -> Option<usize> {
     let mut z = z0;
@@ -110,28 +190,33 @@ pub fn escape_time(
 /// Calculate color based on the `escape_time` of the each pixel using parallel iterators
 ///
 /// # Parameters
-/// - `image_width`, `image_height`: image resolution  
+/// - `image_width`, `image_height`: image resolution
 /// - `origin`: the origin of the viewing rectangular area on the complex plane
 /// - `iteration_max`: The amount of iterations to cutoff and consider a point part of the mandelbrot set
+/// - `fractal_kind`: which iteration formula to use
 ///
 /// # Returns
 /// - `Vec<Color>`: The color data of each pixel serialized by rows
-pub fn calculate_mandelbrot_color_data(
+pub fn calculate_mandelbrot_color_data<T: Float>(
     image_width: usize,
     image_height: usize,
-    center: Complex<f32>,
-    dimensions: Complex<f32>,
+    center: Complex<T>,
+    dimensions: Complex<T>,
     iteration_max: usize,
+    fractal_kind: FractalKind,
 ) -> Vec<Color> {
+    let zero = Complex::new(T::zero(), T::zero());
+
     (0..image_height)
         .into_par_iter()
         .flat_map(|y| {
             (0..image_width).into_par_iter().map(move |x| {
                 // turn pixel position into a specific complex number
                 let c = pixel_to_complex(x, y, image_width, image_height, center, dimensions);
+                let bound = fractal_kind.escape_radius_sqr(c);
 
                 // calculate the mandelbrot equation the specified amount of iterations
-                let escape_time = escape_time(Complex::ZERO, |z| z * z + c, 4.0, iteration_max);
+                let escape_time = escape_time(zero, fractal_kind.iteration_fn(c), bound, iteration_max);
 
                 // calculate color of the specific complex number
                 escape_time_to_grayscale(escape_time)
@@ -140,12 +225,12 @@ pub fn calculate_mandelbrot_color_data(
         .collect()
 }
 
-pub fn escape_time_and_path(
-    z0: Complex<f32>,
-    mut zn: impl FnMut(Complex<f32>) -> Complex<f32>,
-    bound: f32,
+pub fn escape_time_and_path<T: Float>(
+    z0: Complex<T>,
+    mut zn: impl FnMut(Complex<T>) -> Complex<T>,
+    bound: T,
     iteration_max: usize,
-) -> (Option<usize>, Vec<Complex<f32>>) {
+) -> (Option<usize>, Vec<Complex<T>>) {
     let mut z = z0;
     let mut zs = vec![z];
     for n in 0..iteration_max {
@@ -158,22 +243,386 @@ pub fn escape_time_and_path(
     (None, zs)
 }
 
-pub fn calculate_mandelbrot_escape_times_and_paths(
+pub fn calculate_mandelbrot_escape_times_and_paths<T: Float>(
     image_width: usize,
     image_height: usize,
-    center: Complex<f32>,
-    dimensions: Complex<f32>,
+    center: Complex<T>,
+    dimensions: Complex<T>,
     iteration_max: usize,
-) -> Vec<(Option<usize>, Vec<Complex<f32>>)> {
+    fractal_kind: FractalKind,
+) -> Vec<(Option<usize>, Vec<Complex<T>>)> {
     (0..image_height)
         .into_par_iter()
         .flat_map(|y| {
             (0..image_width).into_par_iter().map(move |x| {
                 // turn pixel position into a specific complex number
                 let c = pixel_to_complex(x, y, image_width, image_height, center, dimensions);
+                let bound = fractal_kind.escape_radius_sqr(c);
 
                 // calculate the mandelbrot equation the specified amount of iterations
-                escape_time_and_path(c, |z| z * z + c, 4.0, iteration_max)
+                escape_time_and_path(c, fractal_kind.iteration_fn(c), bound, iteration_max)
+            })
+        })
+        .collect()
+}
+
+/// Inverse of [`pixel_to_complex`]: map a complex point back onto the pixel grid for the same viewing rectangle.
+///
+/// # Parameters
+/// - `c`: the complex point to locate
+/// - `image_width`, `image_height`: image resolution
+/// - `center`, `dimensions`: the same viewing rectangle passed to [`pixel_to_complex`]
+///
+/// # Returns
+/// - `Some((pixel_x, pixel_y))`: when `c` falls within the pixel grid
+/// - `None`: when `c` falls outside the viewing rectangle
+pub fn complex_to_pixel<T: Float>(
+    c: Complex<T>,
+    image_width: usize,
+    image_height: usize,
+    center: Complex<T>,
+    dimensions: Complex<T>,
+) -> Option<(usize, usize)> {
+    let two = T::one() + T::one();
+    let top_left = center - dimensions / two;
+    let offset = c - top_left;
+
+    let horizontal_ratio = offset.re / dimensions.re;
+    let vertical_ratio = offset.im / dimensions.im;
+
+    if !(T::zero()..T::one()).contains(&horizontal_ratio)
+        || !(T::zero()..T::one()).contains(&vertical_ratio)
+    {
+        return None;
+    }
+
+    let pixel_x = (horizontal_ratio * T::from(image_width).unwrap())
+        .to_usize()
+        .unwrap();
+    let pixel_y = (vertical_ratio * T::from(image_height).unwrap())
+        .to_usize()
+        .unwrap();
+
+    Some((pixel_x, pixel_y))
+}
+
+/// Controls how raw Buddhabrot histogram counts are normalized into `0..=255`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DensityScale {
+    /// `count / max_count`
+    Linear,
+    /// `ln(count + 1) / ln(max_count + 1)`, which keeps faint, rarely visited trails visible
+    Logarithmic,
+}
+impl DensityScale {
+    fn normalize(&self, count: u32, max_count: u32) -> u8 {
+        if max_count == 0 {
+            return 0;
+        }
+
+        let ratio = match self {
+            DensityScale::Linear => count as f32 / max_count as f32,
+            DensityScale::Logarithmic => {
+                (count as f32 + 1.0).ln() / (max_count as f32 + 1.0).ln()
+            }
+        };
+
+        (ratio.clamp(0.0, 1.0) * 255.0) as u8
+    }
+}
+
+/// Samples `c` values across (and slightly beyond) the viewing rectangle, keeps only the orbits that escape, and
+/// splats every intermediate `z` of each escaping orbit onto the pixel grid. Each rayon worker accumulates into its
+/// own histogram so no locking is needed; the per-thread histograms are merged once all samples are processed.
+///
+/// # Parameters
+/// - `image_width`, `image_height`: image resolution
+/// - `center`, `dimensions`: viewing rectangle on the complex plane
+/// - `iteration_max`: cutoff used for the escape check
+/// - `sample_count`: how many `c` values to trial along each axis (total samples is `sample_count * sample_count`)
+///
+/// # Returns
+/// - `Vec<u32>`: row-major hit counts for every pixel
+fn accumulate_buddhabrot_histogram<T: Float>(
+    image_width: usize,
+    image_height: usize,
+    center: Complex<T>,
+    dimensions: Complex<T>,
+    iteration_max: usize,
+    sample_count: usize,
+) -> Vec<u32> {
+    let bound = T::from(4.0).unwrap();
+    let zero = Complex::new(T::zero(), T::zero());
+    // sample slightly beyond the viewing rectangle so orbits that pass through it but originate outside are counted
+    let sample_dimensions = dimensions.scale(T::from(1.5).unwrap());
+
+    (0..sample_count)
+        .into_par_iter()
+        .fold(
+            || vec![0u32; image_width * image_height],
+            |mut histogram, sample_y| {
+                for sample_x in 0..sample_count {
+                    let c: Complex<T> = pixel_to_complex(
+                        sample_x,
+                        sample_y,
+                        sample_count,
+                        sample_count,
+                        center,
+                        sample_dimensions,
+                    );
+
+                    // the textbook Buddhabrot recurrence starts every orbit at z0 = 0, not z0 = c
+                    let (escape_time, path) =
+                        escape_time_and_path(zero, |z| z * z + c, bound, iteration_max);
+
+                    if escape_time.is_none() {
+                        continue;
+                    }
+
+                    for z in path {
+                        if let Some((pixel_x, pixel_y)) =
+                            complex_to_pixel(z, image_width, image_height, center, dimensions)
+                        {
+                            histogram[pixel_y * image_width + pixel_x] += 1;
+                        }
+                    }
+                }
+                histogram
+            },
+        )
+        .reduce(
+            || vec![0u32; image_width * image_height],
+            |mut a, b| {
+                for (a, b) in a.iter_mut().zip(b) {
+                    *a += b;
+                }
+                a
+            },
+        )
+}
+
+/// Render a Buddhabrot: a histogram of every point visited by escaping orbits, normalized into a grayscale [`Color`]
+/// per pixel.
+///
+/// # Parameters
+/// - `image_width`, `image_height`: image resolution
+/// - `center`, `dimensions`: viewing rectangle on the complex plane
+/// - `iteration_max`: cutoff used for the escape check; higher values reveal finer orbit structure
+/// - `sample_count`: how many `c` values to trial along each axis (total samples is `sample_count * sample_count`)
+/// - `scale`: how raw hit counts are normalized into `0..=255`
+///
+/// # Returns
+/// - `Vec<Color>`: The color data of each pixel serialized by rows
+pub fn calculate_buddhabrot_density<T: Float>(
+    image_width: usize,
+    image_height: usize,
+    center: Complex<T>,
+    dimensions: Complex<T>,
+    iteration_max: usize,
+    sample_count: usize,
+    scale: DensityScale,
+) -> Vec<Color> {
+    let histogram = accumulate_buddhabrot_histogram(
+        image_width,
+        image_height,
+        center,
+        dimensions,
+        iteration_max,
+        sample_count,
+    );
+
+    let max_count = histogram.iter().copied().max().unwrap_or(0);
+
+    histogram
+        .into_par_iter()
+        .map(|count| {
+            let intensity = scale.normalize(count, max_count);
+            Color([intensity, intensity, intensity, 255])
+        })
+        .collect()
+}
+
+/// Render a "nebulabrot": three Buddhabrot passes at different `iteration_max` values, packed into the red, green,
+/// and blue channels of each [`Color`] respectively.
+///
+/// # Parameters
+/// - `image_width`, `image_height`: image resolution
+/// - `center`, `dimensions`: viewing rectangle on the complex plane
+/// - `red_iteration_max`, `green_iteration_max`, `blue_iteration_max`: per-channel escape cutoffs
+/// - `sample_count`: how many `c` values to trial along each axis (total samples is `sample_count * sample_count`)
+/// - `scale`: how raw hit counts are normalized into `0..=255`
+///
+/// # Returns
+/// - `Vec<Color>`: The color data of each pixel serialized by rows
+pub fn calculate_nebulabrot_density<T: Float>(
+    image_width: usize,
+    image_height: usize,
+    center: Complex<T>,
+    dimensions: Complex<T>,
+    red_iteration_max: usize,
+    green_iteration_max: usize,
+    blue_iteration_max: usize,
+    sample_count: usize,
+    scale: DensityScale,
+) -> Vec<Color> {
+    let channel = |iteration_max: usize| -> Vec<u8> {
+        let histogram = accumulate_buddhabrot_histogram(
+            image_width,
+            image_height,
+            center,
+            dimensions,
+            iteration_max,
+            sample_count,
+        );
+        let max_count = histogram.iter().copied().max().unwrap_or(0);
+        histogram
+            .into_iter()
+            .map(|count| scale.normalize(count, max_count))
+            .collect()
+    };
+
+    let red = channel(red_iteration_max);
+    let green = channel(green_iteration_max);
+    let blue = channel(blue_iteration_max);
+
+    red.into_iter()
+        .zip(green)
+        .zip(blue)
+        .map(|((r, g), b)| Color([r, g, b, 255]))
+        .collect()
+}
+
+/// Like [`escape_time`], but also returns the final `z` at the moment of escape so the caller can compute a
+/// continuous (smoothed) iteration count instead of banding on the raw integer `n`.
+pub fn escape_time_smooth<T: Float>(
+    z0: Complex<T>,
+    mut f: impl FnMut(Complex<T>) -> Complex<T>,
+    bound: T,
+    iteration_max: usize,
+) -> Option<(usize, Complex<T>)> {
+    let mut z = z0;
+    for n in 0..iteration_max {
+        z = f(z);
+        if z.norm_sqr() > bound {
+            return Some((n, z));
+        }
+    }
+    None
+}
+
+/// Turns an `(n, z)` pair from [`escape_time_smooth`] into the continuous iteration count
+/// `mu = n + 1 - ln(ln(|z|)) / ln(2)`, which removes the banding [`escape_time_to_grayscale`] shows at low
+/// `iteration_max`.
+pub fn smooth_iteration_count<T: Float>(n: usize, z: Complex<T>) -> T {
+    let half = T::from(0.5).unwrap();
+    let ln_2 = T::from(std::f64::consts::LN_2).unwrap();
+
+    T::from(n).unwrap() + T::one() - (z.norm_sqr().ln() * half).ln() / ln_2
+}
+
+/// Maps a continuous escape value to a [`Color`], independent of the iteration kernel that produced it.
+///
+/// # Parameters
+/// - `mu`: `Some(continuous_iteration_count)` for points that escaped, `None` for points that never did
+/// - `iteration_max`: the cutoff used for the escape computation, for palettes that normalize against it
+pub trait Palette: Sync {
+    fn color(&self, mu: Option<f32>, iteration_max: usize) -> Color;
+}
+
+/// A handful of ready-made [`Palette`] implementations.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum BuiltinPalette {
+    /// Grayscale driven by the smoothed count instead of the raw iteration count
+    Grayscale,
+    /// Linear interpolation between two colors across `0..iteration_max`
+    Gradient(Color, Color),
+    /// Sweeps hue around the color wheel proportional to the smoothed count, at full saturation and value
+    Hsv,
+}
+impl Palette for BuiltinPalette {
+    fn color(&self, mu: Option<f32>, iteration_max: usize) -> Color {
+        let Some(mu) = mu else {
+            return Color([0, 0, 0, 255]);
+        };
+        let t = (mu / iteration_max as f32).clamp(0.0, 1.0);
+
+        match self {
+            BuiltinPalette::Grayscale => {
+                let intensity = (t * 255.0) as u8;
+                Color([intensity, intensity, intensity, 255])
+            }
+            BuiltinPalette::Gradient(from, to) => Color([
+                lerp_u8(from.red(), to.red(), t),
+                lerp_u8(from.green(), to.green(), t),
+                lerp_u8(from.blue(), to.blue(), t),
+                255,
+            ]),
+            BuiltinPalette::Hsv => hsv_to_color(t * 360.0, 1.0, 1.0),
+        }
+    }
+}
+
+fn lerp_u8(from: u8, to: u8, t: f32) -> u8 {
+    (from as f32 + (to as f32 - from as f32) * t) as u8
+}
+
+/// Converts HSV (`hue` in `0.0..360.0`, `saturation`/`value` in `0.0..=1.0`) to an opaque RGB [`Color`].
+fn hsv_to_color(hue: f32, saturation: f32, value: f32) -> Color {
+    let c = value * saturation;
+    let h_prime = (hue / 60.0).rem_euclid(6.0);
+    let x = c * (1.0 - (h_prime.rem_euclid(2.0) - 1.0).abs());
+    let (r1, g1, b1) = match h_prime as u32 {
+        0 => (c, x, 0.0),
+        1 => (x, c, 0.0),
+        2 => (0.0, c, x),
+        3 => (0.0, x, c),
+        4 => (x, 0.0, c),
+        _ => (c, 0.0, x),
+    };
+    let m = value - c;
+    Color([
+        ((r1 + m) * 255.0) as u8,
+        ((g1 + m) * 255.0) as u8,
+        ((b1 + m) * 255.0) as u8,
+        255,
+    ])
+}
+
+/// Like [`calculate_mandelbrot_color_data`], but colors each pixel with a [`Palette`] driven by the continuous
+/// (smoothed) escape count instead of banding on the raw iteration count.
+///
+/// # Parameters
+/// - `image_width`, `image_height`: image resolution
+/// - `center`, `dimensions`: viewing rectangle on the complex plane
+/// - `iteration_max`: The amount of iterations to cutoff and consider a point part of the mandelbrot set
+/// - `fractal_kind`: which iteration formula to use
+/// - `palette`: maps each pixel's continuous escape value to a [`Color`]
+///
+/// # Returns
+/// - `Vec<Color>`: The color data of each pixel serialized by rows
+pub fn calculate_mandelbrot_color_data_with<T: Float>(
+    image_width: usize,
+    image_height: usize,
+    center: Complex<T>,
+    dimensions: Complex<T>,
+    iteration_max: usize,
+    fractal_kind: FractalKind,
+    palette: &impl Palette,
+) -> Vec<Color> {
+    let zero = Complex::new(T::zero(), T::zero());
+
+    (0..image_height)
+        .into_par_iter()
+        .flat_map(|y| {
+            (0..image_width).into_par_iter().map(move |x| {
+                let c = pixel_to_complex(x, y, image_width, image_height, center, dimensions);
+                let bound = fractal_kind.escape_radius_sqr(c);
+
+                let escaped =
+                    escape_time_smooth(zero, fractal_kind.iteration_fn(c), bound, iteration_max);
+                let mu = escaped.map(|(n, z)| smooth_iteration_count(n, z).to_f32().unwrap());
+
+                palette.color(mu, iteration_max)
             })
         })
         .collect()
@@ -0,0 +1,44 @@
+use super::Color;
+use std::io::{self, Write};
+
+/// Serializes `color_data` as a binary PPM (P6) image: an ASCII header followed by raw RGB bytes (alpha dropped).
+/// Dependency-free, so it works even when the `image` feature is disabled.
+///
+/// # Parameters
+/// - `writer`: destination for the PPM bytes
+/// - `color_data`: pixel data serialized by rows
+/// - `width`, `height`: image resolution
+pub fn write_ppm(
+    writer: &mut impl Write,
+    color_data: &[Color],
+    width: usize,
+    height: usize,
+) -> io::Result<()> {
+    write!(writer, "P6\n{width} {height}\n255\n")?;
+    for color in color_data {
+        writer.write_all(&[color.red(), color.green(), color.blue()])?;
+    }
+    Ok(())
+}
+
+/// Serializes `color_data` as a binary PGM (P5) image: an ASCII header followed by raw luminance bytes, averaged
+/// from each pixel's r, g, and b channels.
+///
+/// # Parameters
+/// - `writer`: destination for the PGM bytes
+/// - `color_data`: pixel data serialized by rows
+/// - `width`, `height`: image resolution
+pub fn write_pgm(
+    writer: &mut impl Write,
+    color_data: &[Color],
+    width: usize,
+    height: usize,
+) -> io::Result<()> {
+    write!(writer, "P5\n{width} {height}\n255\n")?;
+    for color in color_data {
+        let luminance =
+            ((color.red() as u16 + color.green() as u16 + color.blue() as u16) / 3) as u8;
+        writer.write_all(&[luminance])?;
+    }
+    Ok(())
+}
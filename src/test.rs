@@ -2,6 +2,158 @@ use crate::{image_adapter::colors_to_rgbimage, *};
 use num::Complex;
 use std::time::Instant;
 
+#[test]
+pub fn escape_radius_sqr_uses_classic_radius_for_mandelbrot_and_burning_ship() {
+    let c = Complex::new(3.0_f32, 0.0); // |c| = 3 > 2, should not affect these kinds
+    assert_eq!(FractalKind::Mandelbrot.escape_radius_sqr(c), 4.0);
+    assert_eq!(FractalKind::BurningShip.escape_radius_sqr(c), 4.0);
+}
+
+#[test]
+pub fn escape_radius_sqr_grows_with_c_for_mandelbrot_cubic() {
+    let small_c = Complex::new(1.0_f32, 0.0);
+    assert_eq!(FractalKind::MandelbrotCubic.escape_radius_sqr(small_c), 4.0);
+
+    let large_c = Complex::new(2.5_f32, 0.0);
+    assert_eq!(FractalKind::MandelbrotCubic.escape_radius_sqr(large_c), 6.25);
+}
+
+#[test]
+pub fn mandelbrot_cubic_does_not_escape_too_early_near_the_cubic_boundary() {
+    let c = Complex::new(2.5_f32, 0.0);
+    let bound = FractalKind::MandelbrotCubic.escape_radius_sqr(c);
+    let zero = Complex::new(0.0_f32, 0.0);
+
+    // with the fixed bound of 4.0 this would wrongly report Some(0); the correct bound lets n=0 survive
+    let escape_time = escape_time(zero, FractalKind::MandelbrotCubic.iteration_fn(c), bound, 10);
+
+    assert_eq!(escape_time, Some(1));
+}
+
+#[test]
+pub fn burning_ship_folds_z_into_the_first_quadrant_before_squaring() {
+    let c = Complex::new(-1.0_f32, -1.0);
+    let mut f = FractalKind::BurningShip.iteration_fn(c);
+
+    let z1 = f(Complex::new(0.0, 0.0));
+    assert_eq!(z1, Complex::new(-1.0, -1.0)); // fold(0, 0)^2 + c == c
+
+    let z2 = f(z1); // fold(-1, -1) -> (1, 1); (1 + 1i)^2 == 2i; 2i + c == (-1, 1)
+    assert_eq!(z2, Complex::new(-1.0, 1.0));
+}
+
+#[test]
+pub fn smooth_coloring_produces_non_black_output_for_an_escaping_point() {
+    let image_width = 64;
+    let image_height = 64;
+    let center = Complex::new(-0.4, 0.0);
+    let dimensions = Complex::new(3.0, 3.0);
+    let iteration_max = 50;
+
+    let color_data = calculate_mandelbrot_color_data_with(
+        image_width,
+        image_height,
+        center,
+        dimensions,
+        iteration_max,
+        FractalKind::Mandelbrot,
+        &BuiltinPalette::Grayscale,
+    );
+
+    assert_eq!(color_data.len(), image_width * image_height);
+    assert!(
+        color_data.iter().any(|color| color.red() > 0),
+        "expected at least one escaping, non-black pixel"
+    );
+}
+
+#[test]
+pub fn smooth_coloring_with_mandelbrot_cubic_produces_non_black_output() {
+    let image_width = 64;
+    let image_height = 64;
+    let center = Complex::new(0.0, 0.0);
+    let dimensions = Complex::new(6.0, 6.0);
+    let iteration_max = 50;
+
+    let color_data = calculate_mandelbrot_color_data_with(
+        image_width,
+        image_height,
+        center,
+        dimensions,
+        iteration_max,
+        FractalKind::MandelbrotCubic,
+        &BuiltinPalette::Grayscale,
+    );
+
+    assert_eq!(color_data.len(), image_width * image_height);
+    assert!(
+        color_data.iter().any(|color| color.red() > 0),
+        "expected at least one escaping, non-black pixel"
+    );
+}
+
+#[test]
+pub fn hsv_to_color_matches_known_primary_colors() {
+    assert_eq!(hsv_to_color(0.0, 1.0, 1.0), Color([255, 0, 0, 255]));
+    assert_eq!(hsv_to_color(120.0, 1.0, 1.0), Color([0, 255, 0, 255]));
+    assert_eq!(hsv_to_color(240.0, 1.0, 1.0), Color([0, 0, 255, 255]));
+}
+
+#[test]
+pub fn buddhabrot_smoke() {
+    let image_width = 64;
+    let image_height = 64;
+    let center = Complex::new(-0.4, 0.0);
+    let dimensions = Complex::new(3.0, 3.0);
+    let iteration_max = 50;
+    let sample_count = 64;
+
+    let color_data = calculate_buddhabrot_density(
+        image_width,
+        image_height,
+        center,
+        dimensions,
+        iteration_max,
+        sample_count,
+        DensityScale::Linear,
+    );
+
+    assert_eq!(color_data.len(), image_width * image_height);
+    assert!(
+        color_data.iter().any(|color| color.red() > 0),
+        "expected at least one pixel with non-zero Buddhabrot density"
+    );
+}
+
+#[test]
+pub fn nebulabrot_smoke() {
+    let image_width = 32;
+    let image_height = 32;
+    let center = Complex::new(-0.4, 0.0);
+    let dimensions = Complex::new(3.0, 3.0);
+    let sample_count = 48;
+
+    let color_data = calculate_nebulabrot_density(
+        image_width,
+        image_height,
+        center,
+        dimensions,
+        20,
+        40,
+        60,
+        sample_count,
+        DensityScale::Logarithmic,
+    );
+
+    assert_eq!(color_data.len(), image_width * image_height);
+    assert!(
+        color_data
+            .iter()
+            .any(|color| color.red() > 0 || color.green() > 0 || color.blue() > 0),
+        "expected at least one pixel with non-zero density in some channel"
+    );
+}
+
 #[test]
 pub fn default() {
     let image_width = 800;
@@ -9,6 +161,7 @@ pub fn default() {
     let scale = 4.0;
     let center = Complex::new(-0.4, 0.0);
     let iteration_max = 500;
+    let fractal_kind = FractalKind::Mandelbrot;
 
     let dimensions = Complex::new(image_width as f32, image_height as f32);
     let dimensions = (dimensions / dimensions.norm()).scale(scale);
@@ -21,6 +174,7 @@ pub fn default() {
         center,
         dimensions,
         iteration_max,
+        fractal_kind,
     );
     let color_delta = Instant::now() - start;
 
@@ -60,6 +214,10 @@ pub fn terminal() {
     )
     .unwrap();
     let iteration_max = get_parsed_input("Enter max number of iterations: ").unwrap();
+    let fractal_kind = get_parsed_input::<FractalKind>(
+        "Enter a fractal kind (mandelbrot, mandelbrot_cubic, or burning_ship): ",
+    )
+    .unwrap();
 
     let dimensions = Complex::new(image_width as f32, image_height as f32);
     let dimensions = (dimensions / dimensions.norm()).scale(scale);
@@ -72,6 +230,7 @@ pub fn terminal() {
         center,
         dimensions,
         iteration_max,
+        fractal_kind,
     );
     let color_delta = Instant::now() - start;
 